@@ -448,6 +448,10 @@ impl<M: Matrix, S, P> SeededInteractiveMultiPartyServerKey<M, S, P> {
     pub(super) fn rgsw_cts(&self) -> &[M] {
         &self.rgsw_cts
     }
+
+    pub(super) fn parameters(&self) -> &P {
+        &self.parameters
+    }
 }
 
 /// Seeded single party server key
@@ -1060,6 +1064,10 @@ impl<M: Matrix, S, P> SeededNonInteractiveMultiPartyServerKey<M, S, P> {
             parameters,
         }
     }
+
+    pub(super) fn parameters(&self) -> &P {
+        &self.parameters
+    }
 }
 
 /// This key is equivalent to NonInteractiveServerKeyEvaluationDomain with the
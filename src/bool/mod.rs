@@ -195,6 +195,94 @@ mod impl_bool_frontend {
     }
 }
 
+/// Name passed to `ParameterSelector::from_str` does not name any parameter
+/// variant
+#[cfg(any(feature = "interactive_mp", feature = "non_interactive_mp"))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownParameterSet(pub String);
+
+#[cfg(any(feature = "interactive_mp", feature = "non_interactive_mp"))]
+impl std::fmt::Display for UnknownParameterSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Unknown parameter set {}", self.0)
+    }
+}
+
+#[cfg(any(feature = "interactive_mp", feature = "non_interactive_mp"))]
+impl std::error::Error for UnknownParameterSet {}
+
+/// Reason for rejecting a malformed ciphertext received from a client
+#[cfg(any(feature = "interactive_mp", feature = "non_interactive_mp"))]
+#[derive(Clone, Debug, PartialEq)]
+pub enum MalformedCiphertext {
+    /// RLWE ciphertext has `found` polynomials instead of `expected`
+    WrongPolynomialCount { expected: usize, found: usize },
+    /// Polynomial of RLWE ciphertext has `found` coefficients instead of
+    /// `expected` (i.e. the ring size of the selected parameter set)
+    WrongRingSize { expected: usize, found: usize },
+    /// Polynomial coefficient is not in [0, Q)
+    CoefficientOutOfRange,
+    /// Batch claims to store `count` elements but its RLWE ciphertexts can only
+    /// store `capacity` elements
+    CountExceedsCapacity { count: usize, capacity: usize },
+}
+
+#[cfg(any(feature = "interactive_mp", feature = "non_interactive_mp"))]
+impl std::fmt::Display for MalformedCiphertext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MalformedCiphertext::WrongPolynomialCount { expected, found } => write!(
+                f,
+                "Malformed ciphertext: expected RLWE ciphertext with {expected} polynomials but got {found}"
+            ),
+            MalformedCiphertext::WrongRingSize { expected, found } => write!(
+                f,
+                "Malformed ciphertext: expected polynomial with {expected} coefficients but got {found}"
+            ),
+            MalformedCiphertext::CoefficientOutOfRange => {
+                write!(f, "Malformed ciphertext: polynomial coefficient not in [0, Q)")
+            }
+            MalformedCiphertext::CountExceedsCapacity { count, capacity } => write!(
+                f,
+                "Malformed ciphertext: batch of {count} elements exceeds capacity {capacity}"
+            ),
+        }
+    }
+}
+
+#[cfg(any(feature = "interactive_mp", feature = "non_interactive_mp"))]
+impl std::error::Error for MalformedCiphertext {}
+
+/// Checks that each polynomial in `polys` is a well formed polynomial of RLWE
+/// ciphertext. That is, it has `ring_size` coefficients and each coefficient is
+/// in [0, Q).
+///
+/// Ciphertexts received from clients must be checked before they are unseeded,
+/// key switched, or used in evaluation. Otherwise malformed or mismatched
+/// ciphertexts go unnoticed until decryption.
+#[cfg(any(feature = "interactive_mp", feature = "non_interactive_mp"))]
+pub(crate) fn check_well_formed_rlwe_polys<'a>(
+    polys: impl IntoIterator<Item = &'a [u64]>,
+    ring_size: usize,
+    rlwe_q: &parameters::CiphertextModulus<u64>,
+) -> Result<(), MalformedCiphertext> {
+    use crate::backend::Modulus;
+
+    let max = rlwe_q.largest_unsigned_value();
+    polys.into_iter().try_for_each(|p| {
+        if p.len() != ring_size {
+            Err(MalformedCiphertext::WrongRingSize {
+                expected: ring_size,
+                found: p.len(),
+            })
+        } else if p.iter().any(|v| *v > max) {
+            Err(MalformedCiphertext::CoefficientOutOfRange)
+        } else {
+            Ok(())
+        }
+    })
+}
+
 #[cfg(any(feature = "interactive_mp", feature = "non_interactive_mp"))]
 mod common_mp_enc_dec {
     use itertools::Itertools;
@@ -7,7 +7,9 @@ use crate::{
     utils::{Global, WithLocal},
 };
 
-use super::{evaluator::InteractiveMultiPartyCrs, keys::*, parameters::*, ClientKey};
+use super::{
+    evaluator::InteractiveMultiPartyCrs, keys::*, parameters::*, ClientKey, UnknownParameterSet,
+};
 
 pub(crate) type BoolEvaluator = super::evaluator::BoolEvaluator<
     Vec<Vec<u64>>,
@@ -23,25 +25,123 @@ thread_local! {
 }
 static BOOL_SERVER_KEY: OnceLock<ShoupServerKeyEvaluationDomain<Vec<Vec<u64>>>> = OnceLock::new();
 
+/// Parameters of the server key set in the process. Threads that evaluate
+/// ciphertexts without calling `set_parameter_set` themselves create their
+/// local evaluator with these parameters.
+///
+/// Server key is set once per process. Hence, once server key is set,
+/// selecting a parameter set different from server key's parameters panics.
+static BOOL_SERVER_KEY_PARAMETERS: OnceLock<BoolParameters<u64>> = OnceLock::new();
+
 static MULTI_PARTY_CRS: OnceLock<InteractiveMultiPartyCrs<[u8; 32]>> = OnceLock::new();
 
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum ParameterSelector {
     InteractiveLTE2Party,
     InteractiveLTE4Party,
     InteractiveLTE8Party,
 }
 
+impl ParameterSelector {
+    /// All interactive multi-party parameter variants
+    pub const ALL: [ParameterSelector; 3] = [
+        ParameterSelector::InteractiveLTE2Party,
+        ParameterSelector::InteractiveLTE4Party,
+        ParameterSelector::InteractiveLTE8Party,
+    ];
+
+    /// Name of the parameter variant
+    ///
+    /// Name is what `Display` prints and can be used to select the same
+    /// variant with `ParameterSelector::from_str`. This is useful when client
+    /// and server must agree on the parameter variant via configuration.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ParameterSelector::InteractiveLTE2Party => "interactive-lte-2-party",
+            ParameterSelector::InteractiveLTE4Party => "interactive-lte-4-party",
+            ParameterSelector::InteractiveLTE8Party => "interactive-lte-8-party",
+        }
+    }
+}
+
+impl std::fmt::Display for ParameterSelector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl std::str::FromStr for ParameterSelector {
+    type Err = UnknownParameterSet;
+
+    /// Selects parameter variant by its name (see `ParameterSelector::name`)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ParameterSelector::ALL
+            .into_iter()
+            .find(|v| v.name() == s)
+            .ok_or(UnknownParameterSet(s.to_string()))
+    }
+}
+
 /// Select Interactive multi-party parameter variant
 pub fn set_parameter_set(select: ParameterSelector) {
-    match select {
-        ParameterSelector::InteractiveLTE2Party => {
-            BOOL_EVALUATOR.with_borrow_mut(|v| *v = Some(BoolEvaluator::new(I_2P_LB_SR)));
-        }
-        ParameterSelector::InteractiveLTE4Party => {
-            BOOL_EVALUATOR.with_borrow_mut(|v| *v = Some(BoolEvaluator::new(I_4P)));
+    let parameters = match select {
+        ParameterSelector::InteractiveLTE2Party => I_2P_LB_SR,
+        ParameterSelector::InteractiveLTE4Party => I_4P,
+        ParameterSelector::InteractiveLTE8Party => I_8P,
+    };
+    set_local_parameters(parameters);
+}
+
+/// Creates thread local evaluator with `parameters`
+///
+/// Panics if server key is set and `parameters` are not server key's parameters
+fn set_local_parameters(parameters: BoolParameters<u64>) {
+    if let Some(server_key_parameters) = BOOL_SERVER_KEY_PARAMETERS.get() {
+        assert!(
+            server_key_parameters == &parameters,
+            "Selected parameter set does not match parameters of the server key"
+        );
+    }
+    BOOL_EVALUATOR.with_borrow_mut(|v| *v = Some(BoolEvaluator::new(parameters)));
+}
+
+/// Sets the server key and records its parameters for threads that evaluate
+/// ciphertexts without selecting the parameter set themselves
+fn set_server_key_with_parameters(
+    server_key: ShoupServerKeyEvaluationDomain<Vec<Vec<u64>>>,
+    parameters: &BoolParameters<u64>,
+) {
+    assert!(
+        BOOL_SERVER_KEY_PARAMETERS.set(parameters.clone()).is_ok()
+            && BOOL_SERVER_KEY.set(server_key).is_ok(),
+        "Attempted to set server key twice."
+    );
+}
+
+/// Selects parameter set for tests that work with any parameter set
+///
+/// Tests in the same process share the server key. If another test has already
+/// set the server key, current thread uses server key's parameters instead.
+#[cfg(test)]
+pub(crate) fn set_any_parameter_set() {
+    match BOOL_SERVER_KEY_PARAMETERS.get() {
+        Some(parameters) => {
+            BOOL_EVALUATOR.with_borrow_mut(|v| *v = Some(BoolEvaluator::new(parameters.clone())))
         }
-        ParameterSelector::InteractiveLTE8Party => {
-            BOOL_EVALUATOR.with_borrow_mut(|v| *v = Some(BoolEvaluator::new(I_8P)));
+        None => set_parameter_set(ParameterSelector::InteractiveLTE2Party),
+    }
+}
+
+/// Initialises thread local evaluator with parameters of the server key set in
+/// the process if the current thread has not selected any parameter set.
+///
+/// Server key is set once and shared by all threads, only the evaluator (which
+/// holds scratch space) is thread local. Hence ciphertexts can be evaluated on
+/// any thread without duplicating key material.
+fn init_local_evaluator() {
+    if BOOL_EVALUATOR.with_borrow(|v| v.is_none()) {
+        if let Some(parameters) = BOOL_SERVER_KEY_PARAMETERS.get() {
+            BOOL_EVALUATOR.with_borrow_mut(|v| *v = Some(BoolEvaluator::new(parameters.clone())));
         }
     }
 }
@@ -134,13 +234,14 @@ impl
 {
     /// Sets the server key as a global reference for circuit evaluation
     pub fn set_server_key(&self) {
-        assert!(
-            BOOL_SERVER_KEY
-                .set(ShoupServerKeyEvaluationDomain::from(
-                    ServerKeyEvaluationDomain::<_, _, DefaultSecureRng, NttBackendU64>::from(self),
-                ))
-                .is_ok(),
-            "Attempted to set server key twice."
+        set_server_key_with_parameters(
+            ShoupServerKeyEvaluationDomain::from(ServerKeyEvaluationDomain::<
+                _,
+                _,
+                DefaultSecureRng,
+                NttBackendU64,
+            >::from(self)),
+            self.parameters(),
         );
     }
 }
@@ -160,6 +261,7 @@ impl WithLocal for BoolEvaluator {
     where
         F: Fn(&Self) -> R,
     {
+        init_local_evaluator();
         BOOL_EVALUATOR.with_borrow(|s| func(s.as_ref().expect("Parameters not set")))
     }
 
@@ -167,6 +269,7 @@ impl WithLocal for BoolEvaluator {
     where
         F: Fn(&mut Self) -> R,
     {
+        init_local_evaluator();
         BOOL_EVALUATOR.with_borrow_mut(|s| func(s.as_mut().expect("Parameters not set")))
     }
 
@@ -174,6 +277,7 @@ impl WithLocal for BoolEvaluator {
     where
         F: FnMut(&mut Self) -> R,
     {
+        init_local_evaluator();
         BOOL_EVALUATOR.with_borrow_mut(|s| func(s.as_mut().expect("Parameters not set")))
     }
 }
@@ -311,6 +415,19 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn parameter_selector_from_name_works() {
+        for select in ParameterSelector::ALL {
+            assert_eq!(select.to_string().parse::<ParameterSelector>(), Ok(select));
+        }
+        assert_eq!(
+            "non-interactive-lte-2-party".parse::<ParameterSelector>(),
+            Err(UnknownParameterSet(
+                "non-interactive-lte-2-party".to_string()
+            ))
+        );
+    }
+
     #[test]
     fn batched_fhe_u8s_extract_works() {
         set_parameter_set(ParameterSelector::InteractiveLTE2Party);
@@ -362,26 +479,20 @@ mod tests {
         use super::*;
 
         pub(crate) fn set_single_party_parameter_sets(parameter: BoolParameters<u64>) {
-            BOOL_EVALUATOR.with_borrow_mut(|e| *e = Some(BoolEvaluator::new(parameter)));
+            set_local_parameters(parameter);
         }
 
         // SERVER KEY EVAL (/SHOUP) DOMAIN //
         impl SeededSinglePartyServerKey<Vec<Vec<u64>>, BoolParameters<u64>, [u8; 32]> {
             pub fn set_server_key(&self) {
-                assert!(
-                    BOOL_SERVER_KEY
-                        .set(
-                            ShoupServerKeyEvaluationDomain::from(ServerKeyEvaluationDomain::<
-                                _,
-                                _,
-                                DefaultSecureRng,
-                                NttBackendU64,
-                            >::from(
-                                self
-                            ),)
-                        )
-                        .is_ok(),
-                    "Attempted to set server key twice."
+                set_server_key_with_parameters(
+                    ShoupServerKeyEvaluationDomain::from(ServerKeyEvaluationDomain::<
+                        _,
+                        _,
+                        DefaultSecureRng,
+                        NttBackendU64,
+                    >::from(self)),
+                    &self.parameters,
                 );
             }
         }
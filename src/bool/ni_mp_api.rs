@@ -9,6 +9,7 @@ use crate::{
 };
 
 use super::{
+    check_well_formed_rlwe_polys,
     evaluator::NonInteractiveMultiPartyCrs,
     keys::{
         CommonReferenceSeededNonInteractiveMultiPartyServerKeyShare,
@@ -16,7 +17,7 @@ use super::{
         ShoupNonInteractiveServerKeyEvaluationDomain,
     },
     parameters::{BoolParameters, CiphertextModulus, NI_2P, NI_4P_HB_FR, NI_8P},
-    ClientKey,
+    ClientKey, MalformedCiphertext, UnknownParameterSet,
 };
 
 pub(crate) type BoolEvaluator = super::evaluator::BoolEvaluator<
@@ -34,24 +35,102 @@ thread_local! {
 static BOOL_SERVER_KEY: OnceLock<ShoupNonInteractiveServerKeyEvaluationDomain<Vec<Vec<u64>>>> =
     OnceLock::new();
 
+/// Parameters of the server key set in the process. Threads that evaluate
+/// ciphertexts without calling `set_parameter_set` themselves create their
+/// local evaluator with these parameters.
+///
+/// Server key is set once per process. Hence, once server key is set,
+/// selecting a parameter set different from server key's parameters panics.
+static BOOL_SERVER_KEY_PARAMETERS: OnceLock<BoolParameters<u64>> = OnceLock::new();
+
 static MULTI_PARTY_CRS: OnceLock<NonInteractiveMultiPartyCrs<[u8; 32]>> = OnceLock::new();
 
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum ParameterSelector {
     NonInteractiveLTE2Party,
     NonInteractiveLTE4Party,
     NonInteractiveLTE8Party,
 }
 
-pub fn set_parameter_set(select: ParameterSelector) {
-    match select {
-        ParameterSelector::NonInteractiveLTE2Party => {
-            BOOL_EVALUATOR.with_borrow_mut(|v| *v = Some(BoolEvaluator::new(NI_2P)));
+impl ParameterSelector {
+    /// All non-interactive multi-party parameter variants
+    pub const ALL: [ParameterSelector; 3] = [
+        ParameterSelector::NonInteractiveLTE2Party,
+        ParameterSelector::NonInteractiveLTE4Party,
+        ParameterSelector::NonInteractiveLTE8Party,
+    ];
+
+    /// Name of the parameter variant
+    ///
+    /// Name is what `Display` prints and can be used to select the same
+    /// variant with `ParameterSelector::from_str`. This is useful when client
+    /// and server must agree on the parameter variant via configuration.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ParameterSelector::NonInteractiveLTE2Party => "non-interactive-lte-2-party",
+            ParameterSelector::NonInteractiveLTE4Party => "non-interactive-lte-4-party",
+            ParameterSelector::NonInteractiveLTE8Party => "non-interactive-lte-8-party",
         }
-        ParameterSelector::NonInteractiveLTE4Party => {
-            BOOL_EVALUATOR.with_borrow_mut(|v| *v = Some(BoolEvaluator::new(NI_4P_HB_FR)));
+    }
+}
+
+impl std::fmt::Display for ParameterSelector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl std::str::FromStr for ParameterSelector {
+    type Err = UnknownParameterSet;
+
+    /// Selects parameter variant by its name (see `ParameterSelector::name`)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ParameterSelector::ALL
+            .into_iter()
+            .find(|v| v.name() == s)
+            .ok_or(UnknownParameterSet(s.to_string()))
+    }
+}
+
+pub fn set_parameter_set(select: ParameterSelector) {
+    let parameters = match select {
+        ParameterSelector::NonInteractiveLTE2Party => NI_2P,
+        ParameterSelector::NonInteractiveLTE4Party => NI_4P_HB_FR,
+        ParameterSelector::NonInteractiveLTE8Party => NI_8P,
+    };
+    if let Some(server_key_parameters) = BOOL_SERVER_KEY_PARAMETERS.get() {
+        assert!(
+            server_key_parameters == &parameters,
+            "Selected parameter set does not match parameters of the server key"
+        );
+    }
+    BOOL_EVALUATOR.with_borrow_mut(|v| *v = Some(BoolEvaluator::new(parameters)));
+}
+
+/// Selects parameter set for tests that work with any parameter set
+///
+/// Tests in the same process share the server key. If another test has already
+/// set the server key, current thread uses server key's parameters instead.
+#[cfg(test)]
+pub(crate) fn set_any_parameter_set() {
+    match BOOL_SERVER_KEY_PARAMETERS.get() {
+        Some(parameters) => {
+            BOOL_EVALUATOR.with_borrow_mut(|v| *v = Some(BoolEvaluator::new(parameters.clone())))
         }
-        ParameterSelector::NonInteractiveLTE8Party => {
-            BOOL_EVALUATOR.with_borrow_mut(|v| *v = Some(BoolEvaluator::new(NI_8P)));
+        None => set_parameter_set(ParameterSelector::NonInteractiveLTE2Party),
+    }
+}
+
+/// Initialises thread local evaluator with parameters of the server key set in
+/// the process if the current thread has not selected any parameter set.
+///
+/// Server key is set once and shared by all threads, only the evaluator (which
+/// holds scratch space) is thread local. Hence ciphertexts can be evaluated on
+/// any thread without duplicating key material.
+fn init_local_evaluator() {
+    if BOOL_EVALUATOR.with_borrow(|v| v.is_none()) {
+        if let Some(parameters) = BOOL_SERVER_KEY_PARAMETERS.get() {
+            BOOL_EVALUATOR.with_borrow_mut(|v| *v = Some(BoolEvaluator::new(parameters.clone())));
         }
     }
 }
@@ -124,9 +203,12 @@ impl
             NttBackendU64,
         >::from(self);
         assert!(
-            BOOL_SERVER_KEY
-                .set(ShoupNonInteractiveServerKeyEvaluationDomain::from(eval_key))
-                .is_ok(),
+            BOOL_SERVER_KEY_PARAMETERS
+                .set(self.parameters().clone())
+                .is_ok()
+                && BOOL_SERVER_KEY
+                    .set(ShoupNonInteractiveServerKeyEvaluationDomain::from(eval_key))
+                    .is_ok(),
             "Attempted to set server key twice!"
         );
     }
@@ -146,6 +228,7 @@ impl WithLocal for BoolEvaluator {
     where
         F: Fn(&Self) -> R,
     {
+        init_local_evaluator();
         BOOL_EVALUATOR.with_borrow(|s| func(s.as_ref().expect("Parameters not set")))
     }
 
@@ -153,6 +236,7 @@ impl WithLocal for BoolEvaluator {
     where
         F: Fn(&mut Self) -> R,
     {
+        init_local_evaluator();
         BOOL_EVALUATOR.with_borrow_mut(|s| func(s.as_mut().expect("Parameters not set")))
     }
 
@@ -160,6 +244,7 @@ impl WithLocal for BoolEvaluator {
     where
         F: FnMut(&mut Self) -> R,
     {
+        init_local_evaluator();
         BOOL_EVALUATOR.with_borrow_mut(|s| func(s.as_mut().expect("Parameters not set")))
     }
 }
@@ -183,6 +268,24 @@ pub(super) struct NonInteractiveBatchedFheBools<C> {
     data: Vec<C>,
 }
 
+/// Checks that seeded batch of bool ciphertexts (Vec<RLWE>, Seed) is well
+/// formed for the selected parameter set.
+///
+/// Server must call `validate_seeded_batched_fhe_bools` on batches received
+/// from clients to reject malformed ciphertexts with an error. Unseeding a
+/// malformed batch panics.
+pub fn validate_seeded_batched_fhe_bools<R: AsRef<[u64]>>(
+    value: &(Vec<R>, [u8; 32]),
+) -> Result<(), MalformedCiphertext> {
+    BoolEvaluator::with_local(|e| {
+        check_well_formed_rlwe_polys(
+            value.0.iter().map(|p| p.as_ref()),
+            e.parameters().rlwe_n().0,
+            e.parameters().rlwe_q(),
+        )
+    })
+}
+
 /// Batch of Bool cipphertexts stored as vector of RLWE ciphertexts under the
 /// ideal RLWE secret key `s` of the protocol
 ///
@@ -198,7 +301,10 @@ pub(super) struct BatchedFheBools<C> {
 /// Non interactive multi-party specfic encryptor decryptor routines
 mod impl_enc_dec {
     use crate::{
-        bool::{evaluator::BoolEncoding, keys::NonInteractiveMultiPartyClientKey},
+        bool::{
+            check_well_formed_rlwe_polys, evaluator::BoolEncoding,
+            keys::NonInteractiveMultiPartyClientKey,
+        },
         multi_party::{
             multi_party_aggregate_decryption_shares_and_decrypt, multi_party_decryption_share,
         },
@@ -250,6 +356,10 @@ mod impl_enc_dec {
         /// Unseed the RLWE ciphertexts and store them as vector RLWE
         /// ciphertexts in `NonInteractiveBatchedFheBools`
         fn from(value: &(Vec<M::R>, [u8; 32])) -> Self {
+            if let Err(err) = validate_seeded_batched_fhe_bools(value) {
+                panic!("{err}");
+            }
+
             BoolEvaluator::with_local(|e| {
                 let parameters = e.parameters();
                 let ring_size = parameters.rlwe_n().0;
@@ -400,6 +510,16 @@ mod impl_enc_dec {
         fn key_switch(&self, user_id: usize) -> Mat {
             BoolEvaluator::with_local(|e| {
                 assert!(self.dimension() == (2, e.parameters().rlwe_n().0));
+                // `Self` may be an unseeded RLWE ciphertext received from a client
+                // directly (i.e. without going through the checked unseeding of a
+                // seeded ciphertext). Hence check it before key switching.
+                if let Err(err) = check_well_formed_rlwe_polys(
+                    self.iter().map(|p| p.as_slice()),
+                    e.parameters().rlwe_n().0,
+                    e.parameters().rlwe_q(),
+                ) {
+                    panic!("{err}");
+                }
                 let server_key = BOOL_SERVER_KEY.get().unwrap();
                 let ksk = server_key.ui_to_s_ksk(user_id);
                 let decomposer = e.ni_ui_to_s_ks_decomposer().as_ref().unwrap();
@@ -456,4 +576,80 @@ mod tests {
     };
 
     use super::*;
+
+    #[test]
+    fn validate_seeded_batched_fhe_bools_works() {
+        set_any_parameter_set();
+        let ring_size = BoolEvaluator::with_local(|e| e.parameters().rlwe_n().0);
+
+        assert_eq!(
+            validate_seeded_batched_fhe_bools(&(vec![vec![0u64; ring_size]; 2], [0u8; 32])),
+            Ok(())
+        );
+
+        // seeded RLWE ciphertext with one coefficient missing
+        assert_eq!(
+            validate_seeded_batched_fhe_bools(&(
+                vec![vec![0u64; ring_size], vec![0u64; ring_size - 1]],
+                [0u8; 32]
+            )),
+            Err(MalformedCiphertext::WrongRingSize {
+                expected: ring_size,
+                found: ring_size - 1
+            })
+        );
+
+        // seeded RLWE ciphertext with coefficient >= Q
+        let mut partb = vec![0u64; ring_size];
+        partb[0] = u64::MAX;
+        assert_eq!(
+            validate_seeded_batched_fhe_bools(&(vec![partb], [0u8; 32])),
+            Err(MalformedCiphertext::CoefficientOutOfRange)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Malformed ciphertext")]
+    fn unseeding_rejects_malformed_ciphertexts() {
+        set_any_parameter_set();
+        let ring_size = BoolEvaluator::with_local(|e| e.parameters().rlwe_n().0);
+
+        // seeded RLWE ciphertext with one coefficient missing
+        let seeded = (vec![vec![0u64; ring_size - 1]], [0u8; 32]);
+        let _ = NonInteractiveBatchedFheBools::<Vec<Vec<u64>>>::from(&seeded);
+    }
+
+    #[test]
+    #[should_panic(expected = "Malformed ciphertext")]
+    fn unseeding_rejects_out_of_range_coefficients() {
+        set_any_parameter_set();
+        let ring_size = BoolEvaluator::with_local(|e| e.parameters().rlwe_n().0);
+
+        // seeded RLWE ciphertext with coefficient >= Q
+        let mut partb = vec![0u64; ring_size];
+        partb[0] = u64::MAX;
+        let _ = NonInteractiveBatchedFheBools::<Vec<Vec<u64>>>::from(&(vec![partb], [0u8; 32]));
+    }
+
+    #[test]
+    #[should_panic(expected = "Malformed ciphertext")]
+    fn key_switch_rejects_malformed_ciphertexts() {
+        set_any_parameter_set();
+        let ring_size = BoolEvaluator::with_local(|e| e.parameters().rlwe_n().0);
+
+        // unseeded RLWE ciphertext with coefficient >= Q
+        let rlwe = vec![vec![0u64; ring_size], vec![u64::MAX; ring_size]];
+        let _ = rlwe.key_switch(0);
+    }
+
+    #[test]
+    fn parameter_selector_from_name_works() {
+        for select in ParameterSelector::ALL {
+            assert_eq!(select.to_string().parse::<ParameterSelector>(), Ok(select));
+        }
+        assert_eq!(
+            "interactive-lte-2-party".parse::<ParameterSelector>(),
+            Err(UnknownParameterSet("interactive-lte-2-party".to_string()))
+        );
+    }
 }
@@ -1,7 +1,7 @@
 use itertools::Itertools;
 
 use crate::{
-    bool::BoolEvaluator,
+    bool::{check_well_formed_rlwe_polys, BoolEvaluator, MalformedCiphertext},
     random::{DefaultSecureRng, RandomFillUniformInModulus},
     utils::WithLocal,
     Decryptor, Encryptor, KeySwitchWithId, Matrix, MatrixEntity, MatrixMut, MultiPartyDecryptor,
@@ -73,6 +73,10 @@ where
     /// Unseeds collection of seeded RLWE ciphertext in SeededBatchedFheUint8
     /// and returns as `Self`
     fn from(value: &SeededBatchedFheUint8<M::R, [u8; 32]>) -> Self {
+        if let Err(err) = value.validate() {
+            panic!("{err}");
+        }
+
         BoolEvaluator::with_local(|e| {
             let parameters = e.parameters();
             let ring_size = parameters.rlwe_n().0;
@@ -102,6 +106,35 @@ where
     }
 }
 
+impl<C: Matrix<MatElement = u64>> BatchedFheUint8<C> {
+    /// Checks that `Self` is a well formed batch of RLWE ciphertexts for the
+    /// selected parameter set.
+    ///
+    /// Server must call `validate` on batches received from clients to reject
+    /// malformed ciphertexts with an error. Extracting FheUint8s from a
+    /// malformed batch panics.
+    pub fn validate(&self) -> Result<(), MalformedCiphertext> {
+        BoolEvaluator::with_local(|e| {
+            let ring_size = e.parameters().rlwe_n().0;
+            if let Some(c) = self.data.iter().find(|c| c.as_ref().len() != 2) {
+                return Err(MalformedCiphertext::WrongPolynomialCount {
+                    expected: 2,
+                    found: c.as_ref().len(),
+                });
+            }
+            check_well_formed_rlwe_polys(
+                self.data
+                    .iter()
+                    .flat_map(|c| c.iter_rows().map(|p| p.as_ref())),
+                ring_size,
+                e.parameters().rlwe_q(),
+            )?;
+
+            check_batch_capacity(self.count, self.data.len(), ring_size)
+        })
+    }
+}
+
 impl<C, R> SampleExtractor<FheUint8<R>> for BatchedFheUint8<C>
 where
     C: SampleExtractor<R>,
@@ -175,6 +208,10 @@ where
     /// Unseeds collection of seeded RLWE ciphertext in SeededBatchedFheUint8
     /// and returns as `Self`
     fn from(value: &SeededBatchedFheUint8<M::R, [u8; 32]>) -> Self {
+        if let Err(err) = value.validate() {
+            panic!("{err}");
+        }
+
         BoolEvaluator::with_local(|e| {
             let parameters = e.parameters();
             let ring_size = parameters.rlwe_n().0;
@@ -258,6 +295,39 @@ where
     }
 }
 
+impl<C: AsRef<[u64]>, S> SeededBatchedFheUint8<C, S> {
+    /// Checks that `Self` is a well formed batch of seeded RLWE ciphertexts
+    /// for the selected parameter set.
+    ///
+    /// Server must call `validate` on batches received from clients to reject
+    /// malformed ciphertexts with an error. Unseeding a malformed batch panics.
+    pub fn validate(&self) -> Result<(), MalformedCiphertext> {
+        BoolEvaluator::with_local(|e| {
+            let ring_size = e.parameters().rlwe_n().0;
+            check_well_formed_rlwe_polys(
+                self.data.iter().map(|p| p.as_ref()),
+                ring_size,
+                e.parameters().rlwe_q(),
+            )?;
+
+            check_batch_capacity(self.count, self.data.len(), ring_size)
+        })
+    }
+}
+
+/// Checks that `rlwe_count` RLWE ciphertexts can store `count` FheUint8s
+fn check_batch_capacity(
+    count: usize,
+    rlwe_count: usize,
+    ring_size: usize,
+) -> Result<(), MalformedCiphertext> {
+    let capacity = rlwe_count * ring_size / 8;
+    if count > capacity {
+        return Err(MalformedCiphertext::CountExceedsCapacity { count, capacity });
+    }
+    Ok(())
+}
+
 impl<C, S> SeededBatchedFheUint8<C, S> {
     /// Unseed collection of seeded RLWE ciphertexts of `Self` and returns
     /// `NonInteractiveBatchedFheUint8` with collection of unseeded RLWE
@@ -368,3 +438,122 @@ where
         out
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        bool::{set_any_parameter_set, BoolEvaluator, MalformedCiphertext},
+        utils::WithLocal,
+    };
+
+    use super::{BatchedFheUint8, SeededBatchedFheUint8};
+
+    fn seeded_batch(
+        data: Vec<Vec<u64>>,
+        count: usize,
+    ) -> SeededBatchedFheUint8<Vec<u64>, [u8; 32]> {
+        SeededBatchedFheUint8 {
+            data,
+            seed: [0u8; 32],
+            count,
+        }
+    }
+
+    #[test]
+    fn validate_seeded_batched_fhe_uint8s() {
+        set_any_parameter_set();
+        let ring_size = BoolEvaluator::with_local(|e| e.parameters().rlwe_n().0);
+
+        assert_eq!(
+            seeded_batch(vec![vec![0u64; ring_size]], ring_size / 8).validate(),
+            Ok(())
+        );
+
+        // polynomial with one coefficient missing
+        assert_eq!(
+            seeded_batch(vec![vec![0u64; ring_size - 1]], 1).validate(),
+            Err(MalformedCiphertext::WrongRingSize {
+                expected: ring_size,
+                found: ring_size - 1
+            })
+        );
+
+        // coefficient >= Q
+        let mut partb = vec![0u64; ring_size];
+        partb[1] = u64::MAX;
+        assert_eq!(
+            seeded_batch(vec![partb], 1).validate(),
+            Err(MalformedCiphertext::CoefficientOutOfRange)
+        );
+
+        // more FheUint8s than 1 RLWE ciphertext can store
+        assert_eq!(
+            seeded_batch(vec![vec![0u64; ring_size]], ring_size / 8 + 1).validate(),
+            Err(MalformedCiphertext::CountExceedsCapacity {
+                count: ring_size / 8 + 1,
+                capacity: ring_size / 8
+            })
+        );
+    }
+
+    #[test]
+    fn validate_batched_fhe_uint8s() {
+        set_any_parameter_set();
+        let ring_size = BoolEvaluator::with_local(|e| e.parameters().rlwe_n().0);
+        let batch = |data: Vec<Vec<Vec<u64>>>, count| BatchedFheUint8 { data, count };
+
+        assert_eq!(
+            batch(vec![vec![vec![0u64; ring_size]; 2]], ring_size / 8).validate(),
+            Ok(())
+        );
+
+        // RLWE ciphertext without polynomial `a`
+        assert_eq!(
+            batch(vec![vec![vec![0u64; ring_size]]], 1).validate(),
+            Err(MalformedCiphertext::WrongPolynomialCount {
+                expected: 2,
+                found: 1
+            })
+        );
+
+        // polynomial with one coefficient missing
+        assert_eq!(
+            batch(
+                vec![vec![vec![0u64; ring_size], vec![0u64; ring_size - 1]]],
+                1
+            )
+            .validate(),
+            Err(MalformedCiphertext::WrongRingSize {
+                expected: ring_size,
+                found: ring_size - 1
+            })
+        );
+
+        // coefficient >= Q
+        let mut rlwe = vec![vec![0u64; ring_size]; 2];
+        rlwe[0][1] = u64::MAX;
+        assert_eq!(
+            batch(vec![rlwe], 1).validate(),
+            Err(MalformedCiphertext::CoefficientOutOfRange)
+        );
+
+        // more FheUint8s than 1 RLWE ciphertext can store
+        assert_eq!(
+            batch(vec![vec![vec![0u64; ring_size]; 2]], ring_size / 8 + 1).validate(),
+            Err(MalformedCiphertext::CountExceedsCapacity {
+                count: ring_size / 8 + 1,
+                capacity: ring_size / 8
+            })
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Malformed ciphertext")]
+    fn unseeding_rejects_batch_exceeding_capacity() {
+        set_any_parameter_set();
+        let ring_size = BoolEvaluator::with_local(|e| e.parameters().rlwe_n().0);
+
+        let seeded = seeded_batch(vec![vec![0u64; ring_size]], ring_size / 8 + 1);
+        let _ = BatchedFheUint8::<Vec<Vec<u64>>>::from(&seeded);
+    }
+}
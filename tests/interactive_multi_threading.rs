@@ -0,0 +1,44 @@
+use itertools::Itertools;
+use phantom_zone::*;
+use rand::{thread_rng, Rng, RngCore};
+
+#[test]
+fn fhe_uint8s_evaluate_on_spawned_threads() {
+    set_parameter_set(ParameterSelector::InteractiveLTE2Party);
+    let mut seed = [0u8; 32];
+    thread_rng().fill_bytes(&mut seed);
+    set_common_reference_seed(seed);
+
+    let parties = 2;
+    let cks = (0..parties).map(|_| gen_client_key()).collect_vec();
+
+    // round 1
+    let pk_shares = cks.iter().map(|k| collective_pk_share(k)).collect_vec();
+    let pk = aggregate_public_key_shares(&pk_shares);
+
+    // round 2
+    let server_key_shares = cks
+        .iter()
+        .enumerate()
+        .map(|(user_id, k)| collective_server_key_share(k, user_id, parties, &pk))
+        .collect_vec();
+    let server_key = aggregate_server_key_shares(&server_key_shares);
+    server_key.set_server_key();
+
+    let m0 = thread_rng().gen::<u8>();
+    let m1 = thread_rng().gen::<u8>();
+    let c0_enc = pk.encrypt(vec![m0].as_slice());
+    let c1_enc = pk.encrypt(vec![m1].as_slice());
+    let c0: FheUint8 = c0_enc.extract_at(0);
+    let c1: FheUint8 = c1_enc.extract_at(0);
+
+    // evaluate on a thread that never selected the parameter set
+    let c_out = std::thread::spawn(move || &c0 + &c1).join().unwrap();
+
+    let decryption_shares = cks
+        .iter()
+        .map(|k| k.gen_decryption_share(&c_out))
+        .collect_vec();
+    let m_out = cks[0].aggregate_decryption_shares(&c_out, &decryption_shares);
+    assert_eq!(m_out, m0.wrapping_add(m1));
+}
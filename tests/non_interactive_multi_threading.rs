@@ -0,0 +1,40 @@
+use itertools::Itertools;
+use phantom_zone::*;
+use rand::{thread_rng, Rng, RngCore};
+
+#[test]
+fn fhe_uint8s_evaluate_on_spawned_threads() {
+    set_parameter_set(ParameterSelector::NonInteractiveLTE2Party);
+    let mut seed = [0u8; 32];
+    thread_rng().fill_bytes(&mut seed);
+    set_common_reference_seed(seed);
+
+    let parties = 2;
+    let cks = (0..parties).map(|_| gen_client_key()).collect_vec();
+
+    let m0 = thread_rng().gen::<u8>();
+    let m1 = thread_rng().gen::<u8>();
+    let c0_enc = cks[0].encrypt(vec![m0].as_slice());
+    let c1_enc = cks[1].encrypt(vec![m1].as_slice());
+
+    let server_key_shares = cks
+        .iter()
+        .enumerate()
+        .map(|(user_id, k)| gen_server_key_share(user_id, parties, k))
+        .collect_vec();
+    let server_key = aggregate_server_key_shares(&server_key_shares);
+    server_key.set_server_key();
+
+    let c0: FheUint8 = c0_enc.unseed::<Vec<Vec<u64>>>().key_switch(0).extract_at(0);
+    let c1: FheUint8 = c1_enc.unseed::<Vec<Vec<u64>>>().key_switch(1).extract_at(0);
+
+    // evaluate on a thread that never selected the parameter set
+    let c_out = std::thread::spawn(move || &c0 + &c1).join().unwrap();
+
+    let decryption_shares = cks
+        .iter()
+        .map(|k| k.gen_decryption_share(&c_out))
+        .collect_vec();
+    let m_out = cks[0].aggregate_decryption_shares(&c_out, &decryption_shares);
+    assert_eq!(m_out, m0.wrapping_add(m1));
+}